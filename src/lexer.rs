@@ -1,9 +1,11 @@
+use std::fmt;
 use std::iter::Peekable;
 use std::str::Chars;
 
-#[derive(Debug, PartialEq)]
-pub enum Token {
-    Number(i32),
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Token<'a> {
+    Number(i64),
+    Float(f64),
     Plus,
     Minus,
     Multiply,
@@ -16,132 +18,260 @@ pub enum Token {
     RParenthesis,
     Colon,
     Comma,
-    String(String),
+    String(&'a str),
     Boolean(bool),
     Null,
-    Unknown(char),
 }
 
+/// Byte offsets of a token within the original input, as `start..end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Errors produced while scanning the input into tokens.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LexError {
+    /// A `"` was never followed by a closing `"` before the input ran out.
+    UnterminatedString { span: Span },
+    /// A numeric literal was scanned but didn't parse as an `i64`/`f64`.
+    InvalidNumber { span: Span },
+    /// A character that doesn't start any known token or keyword.
+    UnknownChar { ch: char, span: Span },
+}
+
+impl fmt::Display for LexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LexError::UnterminatedString { span } => {
+                write!(f, "Unterminated string literal at {}..{}", span.start, span.end)
+            }
+            LexError::InvalidNumber { span } => {
+                write!(f, "Invalid number literal at {}..{}", span.start, span.end)
+            }
+            LexError::UnknownChar { ch, span } => {
+                write!(f, "Unknown character {ch:?} at {}..{}", span.start, span.end)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LexError {}
+
 pub struct Lexer<'a> {
+    input: &'a str,
     chars: Peekable<Chars<'a>>,
+    position: usize,
 }
 
 impl<'a> Lexer<'a> {
     pub fn new(input: &'a str) -> Self {
         Lexer {
+            input,
             chars: input.chars().peekable(),
+            position: 0,
         }
     }
 
-    pub fn tokenize(&mut self) -> Vec<Token> {
-        let mut tokens = Vec::new();
-        while let Some(token) = self.next_token() {
-            tokens.push(token);
-        }
-        tokens
+    // Convenience wrapper for callers that want the whole stream at once;
+    // prefer pulling tokens lazily via `next_token`/`Iterator` when feeding a `Parser`.
+    // The CLI itself now streams (see `main`), so this is currently exercised
+    // only by tests and any future non-streaming caller.
+    #[allow(dead_code)]
+    pub fn tokenize(&mut self) -> Result<Vec<(Token<'a>, Span)>, LexError> {
+        self.by_ref().collect()
+    }
+
+    // Advances one char and keeps `position` (a byte offset into the input) in sync,
+    // since `Peekable<Chars>` itself exposes no index.
+    fn bump(&mut self) -> Option<char> {
+        let ch = self.chars.next()?;
+        self.position += ch.len_utf8();
+        Some(ch)
     }
 
-    fn next_token(&mut self) -> Option<Token> {
+    pub fn next_token(&mut self) -> Result<Option<(Token<'a>, Span)>, LexError> {
         // skip whitespace
         let next_char = loop {
-            let ch = self.chars.next()?;
+            let ch = match self.bump() {
+                Some(ch) => ch,
+                None => return Ok(None),
+            };
             if !ch.is_whitespace() {
                 break ch;
             }
         };
+        let start = self.position - next_char.len_utf8();
 
-        match next_char {
-            '+' => Some(Token::Plus),
-            '-' => Some(Token::Minus),
-            '*' => Some(Token::Multiply),
-            '/' => Some(Token::Divide),
-            '{' => Some(Token::LBraces),
-            '}' => Some(Token::RBraces),
-            '[' => Some(Token::LBracket),
-            ']' => Some(Token::RBracket),
-            '(' => Some(Token::LParenthesis),
-            ')' => Some(Token::RParenthesis),
-            ':' => Some(Token::Colon),
-            ',' => Some(Token::Comma),
-            '0'..='9' => Some(self.read_number(next_char)),
-            '"' => Some(self.read_string()),
-            't' | 'f' | 'n' => self.read_keyword(next_char),
-            _ => Some(Token::Unknown(next_char)),
-        }
+        let token = match next_char {
+            '+' => Token::Plus,
+            '-' if matches!(self.chars.peek(), Some(ch) if ch.is_ascii_digit()) => {
+                self.read_number(start)?
+            }
+            '-' => Token::Minus,
+            '*' => Token::Multiply,
+            '/' => Token::Divide,
+            '{' => Token::LBraces,
+            '}' => Token::RBraces,
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            '(' => Token::LParenthesis,
+            ')' => Token::RParenthesis,
+            ':' => Token::Colon,
+            ',' => Token::Comma,
+            '0'..='9' => self.read_number(start)?,
+            '"' => self.read_string(start)?,
+            't' | 'f' | 'n' => self.read_keyword(next_char, start)?,
+            other => {
+                return Err(LexError::UnknownChar {
+                    ch: other,
+                    span: Span { start, end: self.position },
+                })
+            }
+        };
+
+        let end = self.position;
+        Ok(Some((token, Span { start, end })))
     }
 
-    fn read_number(&mut self, first: char) -> Token {
-        //transforms char into number
-        let mut number = first.to_digit(10).unwrap() as i32;
-        //until char is a number run this
-        while let Some(&ch) = self.chars.peek() {
-            if let Some(digit) = ch.to_digit(10) {
-                number = number * 10 + digit as i32; //handle multi digit number 
-                self.chars.next();
-            } else {
-                break;
+    // Scans a JSON number: an optional leading '-', an integer part, an
+    // optional '.' fraction, and an optional 'e'/'E' exponent with optional
+    // sign. Classified as a float only if a fraction or exponent was present.
+    fn read_number(&mut self, start: usize) -> Result<Token<'a>, LexError> {
+        while matches!(self.chars.peek(), Some(ch) if ch.is_ascii_digit()) {
+            self.bump();
+        }
+
+        let mut is_float = false;
+
+        if self.fraction_follows() {
+            is_float = true;
+            self.bump(); // '.'
+            while matches!(self.chars.peek(), Some(ch) if ch.is_ascii_digit()) {
+                self.bump();
+            }
+        }
+
+        if self.exponent_follows() {
+            is_float = true;
+            self.bump(); // 'e' / 'E'
+            if matches!(self.chars.peek(), Some('+') | Some('-')) {
+                self.bump();
             }
+            while matches!(self.chars.peek(), Some(ch) if ch.is_ascii_digit()) {
+                self.bump();
+            }
+        }
+
+        let text = &self.input[start..self.position];
+        let span = Span { start, end: self.position };
+        if is_float {
+            text.parse::<f64>()
+                .map(Token::Float)
+                .map_err(|_| LexError::InvalidNumber { span })
+        } else {
+            text.parse::<i64>()
+                .map(Token::Number)
+                .map_err(|_| LexError::InvalidNumber { span })
+        }
+    }
+
+    // Lookahead-only: is `.` followed by at least one digit? (JSON requires a
+    // digit after the decimal point, so `1.` is not a float literal.)
+    fn fraction_follows(&self) -> bool {
+        let mut lookahead = self.chars.clone();
+        match lookahead.next() {
+            Some('.') => matches!(lookahead.next(), Some(ch) if ch.is_ascii_digit()),
+            _ => false,
         }
-        Token::Number(number)
     }
 
-    fn read_string(&mut self) -> Token {
-        let mut content = String::new();
-        //while char is still a string
-        while let Some(ch) = self.chars.next() {
+    // Lookahead-only: is `e`/`E` followed by an optional sign and at least
+    // one digit?
+    fn exponent_follows(&self) -> bool {
+        let mut lookahead = self.chars.clone();
+        match lookahead.next() {
+            Some('e') | Some('E') => {}
+            _ => return false,
+        }
+        match lookahead.next() {
+            Some('+') | Some('-') => matches!(lookahead.next(), Some(ch) if ch.is_ascii_digit()),
+            Some(ch) => ch.is_ascii_digit(),
+            None => false,
+        }
+    }
+
+    fn read_string(&mut self, quote_start: usize) -> Result<Token<'a>, LexError> {
+        // the opening quote has already been consumed; borrow the content
+        // straight out of `input` instead of copying it char by char
+        let content_start = self.position;
+        while let Some(ch) = self.bump() {
             if ch == '"' {
-                return Token::String(content); //handle unclosed quotes by returning string  
+                let content_end = self.position - '"'.len_utf8();
+                return Ok(Token::String(&self.input[content_start..content_end]));
             }
-            content.push(ch);
         }
-       Token::Unknown('"')
+        Err(LexError::UnterminatedString {
+            span: Span { start: quote_start, end: self.position },
+        })
     }
 
-    fn read_keyword(&mut self, first: char) -> Option<Token> {
-        //adds firts char to buff
-        let mut buf = String::new();
-        buf.push(first);
-
+    fn read_keyword(&mut self, first: char, start: usize) -> Result<Token<'a>, LexError> {
         while let Some(&ch) = self.chars.peek() {
-            //adds all letter chars to buff 
+            //consume all letter chars
             if ch.is_ascii_alphabetic() {
-                buf.push(ch);
-                self.chars.next();
+                self.bump();
             } else {
                 break;
             }
         }
 
-        match buf.as_str() {
-            "true" => Some(Token::Boolean(true)),
-            "false" => Some(Token::Boolean(false)),
-            "null" => Some(Token::Null),
-            _ => Some(Token::Unknown(first)),
+        match &self.input[start..self.position] {
+            "true" => Ok(Token::Boolean(true)),
+            "false" => Ok(Token::Boolean(false)),
+            "null" => Ok(Token::Null),
+            _ => Err(LexError::UnknownChar {
+                ch: first,
+                span: Span { start, end: self.position },
+            }),
         }
     }
 }
 
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Result<(Token<'a>, Span), LexError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_token().transpose()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn just_tokens(tokens: Vec<(Token<'_>, Span)>) -> Vec<Token<'_>> {
+        tokens.into_iter().map(|(tok, _)| tok).collect()
+    }
+
     #[test]
     fn test_json_lexer() {
         let input = r#"{ "key": 123, "active": true, "items": [1, 2, null] }"#;
         let mut lexer = Lexer::new(input);
-        let tokens = lexer.tokenize();
+        let tokens = just_tokens(lexer.tokenize().unwrap());
 
         let expected = vec![
             Token::LBraces,
-            Token::String("key".to_string()),
+            Token::String("key"),
             Token::Colon,
             Token::Number(123),
             Token::Comma,
-            Token::String("active".to_string()),
+            Token::String("active"),
             Token::Colon,
             Token::Boolean(true),
             Token::Comma,
-            Token::String("items".to_string()),
+            Token::String("items"),
             Token::Colon,
             Token::LBracket,
             Token::Number(1),
@@ -159,7 +289,7 @@ mod tests {
     fn test_numbers() {
         let input = "42 007 1234";
         let mut lexer = Lexer::new(input);
-        let tokens = lexer.tokenize();
+        let tokens = just_tokens(lexer.tokenize().unwrap());
 
         assert_eq!(
             tokens,
@@ -171,28 +301,88 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_negative_numbers() {
+        let input = "-1 -42";
+        let mut lexer = Lexer::new(input);
+        let tokens = just_tokens(lexer.tokenize().unwrap());
+
+        assert_eq!(tokens, vec![Token::Number(-1), Token::Number(-42)]);
+    }
+
+    #[test]
+    fn test_minus_operator_not_mistaken_for_number() {
+        let input = "5 - 3";
+        let mut lexer = Lexer::new(input);
+        let tokens = just_tokens(lexer.tokenize().unwrap());
+
+        assert_eq!(
+            tokens,
+            vec![Token::Number(5), Token::Minus, Token::Number(3)]
+        );
+    }
+
+    #[test]
+    fn test_float_numbers() {
+        let input = "2.5 -0.5";
+        let mut lexer = Lexer::new(input);
+        let tokens = just_tokens(lexer.tokenize().unwrap());
+
+        assert_eq!(tokens, vec![Token::Float(2.5), Token::Float(-0.5)]);
+    }
+
+    #[test]
+    fn test_exponent_numbers() {
+        let input = "6.022e23 1E-5 2e+3";
+        let mut lexer = Lexer::new(input);
+        let tokens = just_tokens(lexer.tokenize().unwrap());
+
+        assert_eq!(
+            tokens,
+            vec![Token::Float(6.022e23), Token::Float(1E-5), Token::Float(2e+3)]
+        );
+    }
+
+    #[test]
+    fn test_trailing_dot_is_not_consumed_as_fraction() {
+        // JSON requires at least one digit after '.', so `1.` should lex the
+        // `1` fine and then fail on the bare `.` rather than silently
+        // truncating it into a float.
+        let input = "1.";
+        let mut lexer = Lexer::new(input);
+        let err = lexer.tokenize().unwrap_err();
+
+        assert_eq!(
+            err,
+            LexError::UnknownChar { ch: '.', span: Span { start: 1, end: 2 } }
+        );
+    }
+
     #[test]
     fn test_simple_string() {
         let input = r#""hello""#;
         let mut lexer = Lexer::new(input);
-        let tokens = lexer.tokenize();
-        assert_eq!(tokens, vec![Token::String("hello".to_string())]);
+        let tokens = just_tokens(lexer.tokenize().unwrap());
+        assert_eq!(tokens, vec![Token::String("hello")]);
     }
 
     #[test]
     fn test_unclosed_string() {
         let input = r#""hello"#; // missing closing quote
         let mut lexer = Lexer::new(input);
-        let tokens = lexer.tokenize();
-        // your lexer currently treats it as Token::Unknown('"')
-        assert_eq!(tokens, vec![Token::Unknown('"')]);
+        let err = lexer.tokenize().unwrap_err();
+
+        assert_eq!(
+            err,
+            LexError::UnterminatedString { span: Span { start: 0, end: 6 } }
+        );
     }
 
     #[test]
     fn test_booleans_and_null() {
         let input = "true false null";
         let mut lexer = Lexer::new(input);
-        let tokens = lexer.tokenize();
+        let tokens = just_tokens(lexer.tokenize().unwrap());
         assert_eq!(
             tokens,
             vec![
@@ -207,16 +397,20 @@ mod tests {
     fn test_unknown_keyword() {
         let input = "truth";
         let mut lexer = Lexer::new(input);
-        let tokens = lexer.tokenize();
-        // “truth” is not a valid JSON keyword, should mark first char as unknown
-        assert_eq!(tokens, vec![Token::Unknown('t')]);
+        let err = lexer.tokenize().unwrap_err();
+
+        // “truth” is not a valid JSON keyword
+        assert_eq!(
+            err,
+            LexError::UnknownChar { ch: 't', span: Span { start: 0, end: 5 } }
+        );
     }
 
     #[test]
     fn test_operators_and_punctuation() {
         let input = "+ - * / : , { } [ ] ( )";
         let mut lexer = Lexer::new(input);
-        let tokens = lexer.tokenize();
+        let tokens = just_tokens(lexer.tokenize().unwrap());
 
         assert_eq!(
             tokens,
@@ -241,16 +435,16 @@ mod tests {
     fn test_mixed_json_like_structure() {
         let input = r#"[{"id":1,"ok":false},null]"#;
         let mut lexer = Lexer::new(input);
-        let tokens = lexer.tokenize();
+        let tokens = just_tokens(lexer.tokenize().unwrap());
 
         let expected = vec![
             Token::LBracket,
             Token::LBraces,
-            Token::String("id".to_string()),
+            Token::String("id"),
             Token::Colon,
             Token::Number(1),
             Token::Comma,
-            Token::String("ok".to_string()),
+            Token::String("ok"),
             Token::Colon,
             Token::Boolean(false),
             Token::RBraces,
@@ -266,8 +460,68 @@ mod tests {
     fn test_empty_input() {
         let input = "";
         let mut lexer = Lexer::new(input);
-        let tokens = lexer.tokenize();
+        let tokens = lexer.tokenize().unwrap();
         assert!(tokens.is_empty());
     }
 
+    #[test]
+    fn test_string_token_borrows_from_input() {
+        let input = r#""hello""#;
+        let mut lexer = Lexer::new(input);
+        let tokens = lexer.tokenize().unwrap();
+
+        match tokens.as_slice() {
+            [(Token::String(s), _)] => {
+                // the slice should point right back into `input`, not a fresh allocation
+                let offset = s.as_ptr() as usize - input.as_ptr() as usize;
+                assert_eq!(offset, 1); // just past the opening quote
+                assert_eq!(*s, "hello");
+            }
+            other => panic!("expected a single string token, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_token_spans() {
+        let input = r#"{"a":1}"#;
+        let mut lexer = Lexer::new(input);
+        let tokens = lexer.tokenize().unwrap();
+
+        let spans: Vec<Span> = tokens.into_iter().map(|(_, span)| span).collect();
+        assert_eq!(
+            spans,
+            vec![
+                Span { start: 0, end: 1 }, // {
+                Span { start: 1, end: 4 }, // "a"
+                Span { start: 4, end: 5 }, // :
+                Span { start: 5, end: 6 }, // 1
+                Span { start: 6, end: 7 }, // }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lexer_as_iterator() {
+        let input = "1, true";
+        let lexer = Lexer::new(input);
+        let tokens: Vec<Token> = lexer.map(|r| r.unwrap().0).collect();
+
+        assert_eq!(
+            tokens,
+            vec![Token::Number(1), Token::Comma, Token::Boolean(true)]
+        );
+    }
+
+    #[test]
+    fn test_lexer_iterator_surfaces_lex_error() {
+        let input = r#""oops"#;
+        let mut lexer = Lexer::new(input);
+
+        assert_eq!(
+            lexer.next(),
+            Some(Err(LexError::UnterminatedString { span: Span { start: 0, end: 5 } }))
+        );
+        assert_eq!(lexer.next(), None);
+    }
+
 }
@@ -27,11 +27,9 @@ fn main() {
 };
 
 
-    let mut lexer = Lexer::new(&input);
-    let tokens = lexer.tokenize();
-    println!("Tokens: {tokens:?}");
-
-    let mut parser = Parser::new(tokens.into_iter());
+    // `Lexer` is itself an `Iterator`, so tokens are pulled lazily as the
+    // parser consumes them instead of being materialized into a `Vec` up front.
+    let mut parser = Parser::new(Lexer::new(&input));
    match parser.parse() {
     Ok(ast) => {
         println!("\n Parsed structure:");
@@ -44,10 +42,11 @@ fn main() {
 
 }
 
-fn pretty_print(value: &Type, indent: usize) {
+fn pretty_print(value: &Type<'_>, indent: usize) {
     let space = "  ".repeat(indent);
     match value {
         Type::Number(n) => println!("{space}{n}"),
+        Type::Float(n) => println!("{space}{n}"),
         Type::String(s) => println!("{space}\"{s}\""),
         Type::Boolean(b) => println!("{space}{b}"),
         Type::Null => println!("{space}null"),
@@ -1,28 +1,76 @@
-use crate::lexer::Token;
-use std::{iter::Peekable};
+use crate::lexer::{LexError, Span, Token};
+use std::fmt;
+use std::iter::Peekable;
 
 #[derive(Debug, PartialEq)]
-pub enum Type {
-    Object(Vec<(String, Type)>),
-    Array(Vec<Type>),
-    String(String),
-    Number(i32),
+pub enum Type<'a> {
+    Object(Vec<(&'a str, Type<'a>)>),
+    Array(Vec<Type<'a>>),
+    String(&'a str),
+    Number(i64),
+    Float(f64),
     Boolean(bool),
     Null,
 }
 
-pub struct Parser<I>
+/// Errors produced while turning a token stream into a `Type`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    UnexpectedToken {
+        found: String,
+        expected: &'static str,
+        span: Span,
+    },
+    UnexpectedEof {
+        expected: &'static str,
+    },
+    TrailingTokens {
+        span: Span,
+    },
+    Lex(LexError),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedToken { found, expected, span } => write!(
+                f,
+                "Expected {expected}, found {found} at {}..{}",
+                span.start, span.end
+            ),
+            ParseError::UnexpectedEof { expected } => {
+                write!(f, "Unexpected end of input, expected {expected}")
+            }
+            ParseError::TrailingTokens { span } => write!(
+                f,
+                "Extra tokens after valid JSON value at {}..{}",
+                span.start, span.end
+            ),
+            ParseError::Lex(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<LexError> for ParseError {
+    fn from(e: LexError) -> Self {
+        ParseError::Lex(e)
+    }
+}
+
+pub struct Parser<'a, I>
 where
-    I: Iterator<Item = Token>,
+    I: Iterator<Item = Result<(Token<'a>, Span), LexError>>,
 {
     tokens: Peekable<I>,
 }
 //parser takes in a stream of tokens (produced through lexer) and turns it into a data structure, Val enum,
 //where each token represents a piece of JSON syntax
 
-impl<I> Parser<I>
+impl<'a, I> Parser<'a, I>
 where
-    I: Iterator<Item = Token>,
+    I: Iterator<Item = Result<(Token<'a>, Span), LexError>>,
 {
     // Constructor
     pub fn new(iter: I) -> Self {
@@ -31,57 +79,93 @@ where
         }
     }
 
-    // Peek at next token without consuming
-    fn peek(&mut self) -> Option<&Token> {
-        self.tokens.peek()
+    // Peek at the next token without consuming it, surfacing a lexing
+    // failure immediately rather than letting it hide behind the peek.
+    fn peek_token(&mut self) -> Result<Option<Token<'a>>, ParseError> {
+        match self.tokens.peek() {
+            Some(Ok((tok, _))) => Ok(Some(*tok)),
+            Some(Err(e)) => Err(e.clone().into()),
+            None => Ok(None),
+        }
     }
 
-    // Consume next token
-    fn next(&mut self) -> Option<Token> {
-        self.tokens.next()
+    // Peek at the span of the next token without consuming
+    fn peek_span(&mut self) -> Option<Span> {
+        match self.tokens.peek() {
+            Some(Ok((_, span))) => Some(*span),
+            _ => None,
+        }
     }
 
-    pub fn parse(&mut self) -> Result<Type, String> {
-    let value = self.parse_val()?;
-    if self.peek().is_some() {
-        return Err("Extra tokens after valid JSON value".into());
+    // Consume the next token, propagating a lexing failure as a `ParseError`
+    fn advance(&mut self) -> Result<Option<(Token<'a>, Span)>, ParseError> {
+        match self.tokens.next() {
+            Some(Ok(tok_span)) => Ok(Some(tok_span)),
+            Some(Err(e)) => Err(e.into()),
+            None => Ok(None),
+        }
+    }
+
+    pub fn parse(&mut self) -> Result<Type<'a>, ParseError> {
+        let value = self.parse_val()?;
+        if self.peek_token()?.is_some() {
+            let span = self.peek_span().expect("just confirmed Some");
+            return Err(ParseError::TrailingTokens { span });
+        }
+        Ok(value)
     }
-    Ok(value)
-}
 
     //decides what kind are we parsing next, parsing the next value in the token stream
-    pub fn parse_val(&mut self) -> Result<Type, String> {
-        match self.next() {
-            Some(Token::Number(n)) => Ok(Type::Number(n)),
-            Some(Token::String(s)) => Ok(Type::String(s)),
-            Some(Token::Boolean(b)) => Ok(Type::Boolean(b)),
-            Some(Token::Null) => Ok(Type::Null),
-            Some(Token::LBraces) => self.parse_object(),
-            Some(Token::LBracket) => self.parse_array(),
-            Some(tok) => Err(format!("Unexpected token: {:?}", tok)),
-            None => Err("Unexpected end of input".into()),
+    pub fn parse_val(&mut self) -> Result<Type<'a>, ParseError> {
+        match self.advance()? {
+            Some((Token::Number(n), _)) => Ok(Type::Number(n)),
+            Some((Token::Float(n), _)) => Ok(Type::Float(n)),
+            Some((Token::String(s), _)) => Ok(Type::String(s)),
+            Some((Token::Boolean(b), _)) => Ok(Type::Boolean(b)),
+            Some((Token::Null, _)) => Ok(Type::Null),
+            Some((Token::LBraces, _)) => self.parse_object(),
+            Some((Token::LBracket, _)) => self.parse_array(),
+            Some((tok, span)) => Err(ParseError::UnexpectedToken {
+                found: format!("{tok:?}"),
+                expected: "a value",
+                span,
+            }),
+            None => Err(ParseError::UnexpectedEof { expected: "a value" }),
         }
     }
     //Parses object structure {} from token. Expects key-value pairs where each key is a string, followed by :, and a value. Pairs are seprated by ,
     //and ends with a }
-    pub fn parse_object(&mut self) -> Result<Type, String> {
-      
+    pub fn parse_object(&mut self) -> Result<Type<'a>, ParseError> {
         let mut kv_pairs = Vec::new();
-        if self.peek() == Some(&Token::RBraces) {
-            self.next();
+        if self.peek_token()? == Some(Token::RBraces) {
+            self.advance()?;
             return Ok(Type::Object(kv_pairs));
         }
         loop {
             //next value is a string and consume (extract the value) and assign to key
-            let key = match self.next() {
-                Some(Token::String(s)) => s,
-                _ => return Err("Expected string key in object".into()),
+            let key = match self.advance()? {
+                Some((Token::String(s), _)) => s,
+                Some((tok, span)) => {
+                    return Err(ParseError::UnexpectedToken {
+                        found: format!("{tok:?}"),
+                        expected: "a string key",
+                        span,
+                    })
+                }
+                None => return Err(ParseError::UnexpectedEof { expected: "a string key" }),
             };
 
             //next value is a colon and consume
-            match self.next() {
-                Some(Token::Colon) => {}
-                _ => return Err("Expected ':' after key".into()),
+            match self.advance()? {
+                Some((Token::Colon, _)) => {}
+                Some((tok, span)) => {
+                    return Err(ParseError::UnexpectedToken {
+                        found: format!("{tok:?}"),
+                        expected: "':'",
+                        span,
+                    })
+                }
+                None => return Err(ParseError::UnexpectedEof { expected: "':'" }),
             }
 
             //parse value
@@ -89,26 +173,33 @@ where
             kv_pairs.push((key, value));
 
             //handle comma, closing braces
-            match self.peek() {
+            match self.peek_token()? {
                 Some(Token::Comma) => {
-                    self.next();
+                    self.advance()?;
                 }
                 Some(Token::RBraces) => {
-                    self.next();
+                    self.advance()?;
                     break;
                 }
-                _ => return Err("Expected ',' or '}' after pair".into()),
+                Some(tok) => {
+                    let span = self.peek_span().expect("just matched Some(_)");
+                    return Err(ParseError::UnexpectedToken {
+                        found: format!("{tok:?}"),
+                        expected: "',' or '}'",
+                        span,
+                    });
+                }
+                None => return Err(ParseError::UnexpectedEof { expected: "',' or '}'" }),
             }
         }
         Ok(Type::Object(kv_pairs))
     }
     //Parses an array structure [] from token stream. Values are separated by , and enclose [], each element is parsed using parse_val, and nested objects
-    pub fn parse_array(&mut self) -> Result<Type, String>{
-      
-        let mut vals:Vec<Type> = Vec::new();
+    pub fn parse_array(&mut self) -> Result<Type<'a>, ParseError> {
+        let mut vals: Vec<Type<'a>> = Vec::new();
 
-        if self.peek() == Some(&Token::RBracket) {
-            self.next();
+        if self.peek_token()? == Some(Token::RBracket) {
+            self.advance()?;
             return Ok(Type::Array(vals));
         }
 
@@ -116,15 +207,23 @@ where
             let value = self.parse_val()?;
             vals.push(value);
 
-            match self.peek() {
+            match self.peek_token()? {
                 Some(Token::Comma) => {
-                    self.next(); //consume and go on
+                    self.advance()?; //consume and go on
                 }
                 Some(Token::RBracket) => {
-                    self.next();
+                    self.advance()?;
                     break;
                 }
-                _ => return  Err("Expected ',' or ']' after an array value".into())
+                Some(tok) => {
+                    let span = self.peek_span().expect("just matched Some(_)");
+                    return Err(ParseError::UnexpectedToken {
+                        found: format!("{tok:?}"),
+                        expected: "',' or ']'",
+                        span,
+                    });
+                }
+                None => return Err(ParseError::UnexpectedEof { expected: "',' or ']'" }),
             }
         }
 
@@ -132,14 +231,23 @@ where
     }
 }
 
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::lexer::Token;
 
-    fn parse_tokens(tokens: Vec<Token>) -> Result<Type, String> {
-        let mut parser = Parser::new(tokens.into_iter());
+    // Wraps bare tokens with a throwaway span and an infallible `Ok` for
+    // tests that only care about the parsed structure, not source positions
+    // or lexer failures.
+    fn dummy_span(tokens: Vec<Token<'_>>) -> Vec<Result<(Token<'_>, Span), LexError>> {
+        tokens
+            .into_iter()
+            .map(|tok| Ok((tok, Span { start: 0, end: 0 })))
+            .collect()
+    }
+
+    fn parse_tokens(tokens: Vec<Token<'_>>) -> Result<Type<'_>, ParseError> {
+        let mut parser = Parser::new(dummy_span(tokens).into_iter());
         parser.parse_val()
     }
 
@@ -150,11 +258,18 @@ mod tests {
         assert_eq!(result, Type::Number(42));
     }
 
+    #[test]
+    fn test_parse_float() {
+        let tokens = vec![Token::Float(2.5)];
+        let result = parse_tokens(tokens).unwrap();
+        assert_eq!(result, Type::Float(2.5));
+    }
+
     #[test]
     fn test_parse_string() {
-        let tokens = vec![Token::String("hello".into())];
+        let tokens = vec![Token::String("hello")];
         let result = parse_tokens(tokens).unwrap();
-        assert_eq!(result, Type::String("hello".into()));
+        assert_eq!(result, Type::String("hello"));
     }
 
     #[test]
@@ -182,11 +297,11 @@ mod tests {
     fn test_parse_object_with_values() {
         let tokens = vec![
             Token::LBraces,
-            Token::String("a".into()),
+            Token::String("a"),
             Token::Colon,
             Token::Number(1),
             Token::Comma,
-            Token::String("b".into()),
+            Token::String("b"),
             Token::Colon,
             Token::Boolean(false),
             Token::RBraces,
@@ -195,8 +310,8 @@ mod tests {
         assert_eq!(
             result,
             Type::Object(vec![
-                ("a".into(), Type::Number(1)),
-                ("b".into(), Type::Boolean(false))
+                ("a", Type::Number(1)),
+                ("b", Type::Boolean(false))
             ])
         );
     }
@@ -214,7 +329,7 @@ mod tests {
             Token::LBracket,
             Token::Number(1),
             Token::Comma,
-            Token::String("x".into()),
+            Token::String("x"),
             Token::Comma,
             Token::Boolean(true),
             Token::RBracket,
@@ -224,7 +339,7 @@ mod tests {
             result,
             Type::Array(vec![
                 Type::Number(1),
-                Type::String("x".into()),
+                Type::String("x"),
                 Type::Boolean(true)
             ])
         );
@@ -234,7 +349,7 @@ mod tests {
     fn test_parse_nested_structures() {
         let tokens = vec![
             Token::LBraces,
-            Token::String("arr".into()),
+            Token::String("arr"),
             Token::Colon,
             Token::LBracket,
             Token::Number(1),
@@ -242,7 +357,7 @@ mod tests {
             Token::Number(2),
             Token::Comma,
             Token::LBraces,
-            Token::String("x".into()),
+            Token::String("x"),
             Token::Colon,
             Token::Null,
             Token::RBraces,
@@ -254,11 +369,11 @@ mod tests {
         assert_eq!(
             result,
             Type::Object(vec![(
-                "arr".into(),
+                "arr",
                 Type::Array(vec![
                     Type::Number(1),
                     Type::Number(2),
-                    Type::Object(vec![("x".into(), Type::Null)])
+                    Type::Object(vec![("x", Type::Null)])
                 ])
             )])
         );
@@ -266,7 +381,7 @@ mod tests {
 
     #[test]
     fn test_parse_object_missing_colon_error() {
-        let tokens = vec![Token::LBraces, Token::String("a".into()), Token::Number(1)];
+        let tokens = vec![Token::LBraces, Token::String("a"), Token::Number(1)];
         let result = parse_tokens(tokens);
         assert!(result.is_err());
     }
@@ -277,4 +392,37 @@ mod tests {
         let result = parse_tokens(tokens);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_parse_error_includes_span() {
+        let tokens = vec![
+            Ok((Token::LBraces, Span { start: 0, end: 1 })),
+            Ok((Token::String("a"), Span { start: 1, end: 4 })),
+            Ok((Token::Number(1), Span { start: 5, end: 6 })),
+        ];
+        let mut parser = Parser::new(tokens.into_iter());
+        let err = parser.parse_val().unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::UnexpectedToken {
+                found: "Number(1)".to_string(),
+                expected: "':'",
+                span: Span { start: 5, end: 6 },
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_propagates_lex_error() {
+        let tokens: Vec<Result<(Token<'_>, Span), LexError>> = vec![
+            Ok((Token::LBraces, Span { start: 0, end: 1 })),
+            Err(LexError::UnterminatedString { span: Span { start: 1, end: 7 } }),
+        ];
+        let mut parser = Parser::new(tokens.into_iter());
+        let err = parser.parse_val().unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::Lex(LexError::UnterminatedString { span: Span { start: 1, end: 7 } })
+        );
+    }
 }